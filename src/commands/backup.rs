@@ -1,6 +1,6 @@
 //! `backup` subcommand
 
-use std::path::PathBuf;
+use std::{net::IpAddr, path::PathBuf};
 
 use crate::{
     commands::{init::init, snapshots::fill_table},
@@ -22,6 +22,7 @@ use serde_with::serde_as;
 use rustic_core::{
     BackupOptions, CommandInput, ConfigOptions, IndexedIds, KeyOptions, LocalSourceFilterOptions,
     LocalSourceSaveOptions, ParentOptions, PathList, ProgressBars, Repository, SnapshotOptions,
+    StringList,
 };
 
 /// `backup` subcommand
@@ -42,6 +43,22 @@ pub struct BackupCmd {
     #[serde(skip)]
     cli_sources: Vec<String>,
 
+    /// Read backup sources from the given file (can be specified multiple times). The file contains one
+    /// source per line; empty lines and lines starting with # are ignored
+    #[clap(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    #[merge(skip)]
+    #[serde(skip)]
+    files_from: Vec<PathBuf>,
+
+    /// Create a separate snapshot for each source given on the command line (or using --files-from) instead of a
+    /// single snapshot containing all sources. Note that all snapshots are deduplicated against the repository
+    /// content present when the command started, so data contained in several of the sources may be saved
+    /// more than once - like for multiple sources defined in the config file
+    #[clap(long, conflicts_with = "as_path")]
+    #[merge(skip)]
+    #[serde(skip)]
+    split_sources: bool,
+
     /// Set filename to be used when backing up from stdin
     #[clap(long, value_name = "FILENAME", default_value = "stdin", value_hint = ValueHint::FilePath)]
     #[merge(skip)]
@@ -67,6 +84,25 @@ pub struct BackupCmd {
     #[merge(strategy=conflate::bool::overwrite_false)]
     pub no_scan: bool,
 
+    /// Strip the domain from the host name saved in the snapshot (e.g. use "host" for "host.example.com").
+    /// Host names given by --host and IP addresses are not changed
+    #[clap(long)]
+    #[merge(strategy=conflate::bool::overwrite_false)]
+    short_host: bool,
+
+    /// Additional command line option whose value is replaced by "***" in the command saved in the
+    /// snapshot (can be specified multiple times). "--password" and "--password-command" are always
+    /// redacted
+    #[clap(long = "redact-option", value_name = "OPTION")]
+    #[merge(strategy=conflate::vec::append)]
+    redact_options: Vec<String>,
+
+    /// Tag which must be set for the snapshot (can be specified multiple times). A tag of the form
+    /// "key=value" fulfills the required tag "key"
+    #[clap(long = "required-tag", value_name = "TAG")]
+    #[merge(strategy=conflate::vec::append)]
+    required_tags: Vec<String>,
+
     /// Output generated snapshot in json format
     #[clap(long)]
     #[merge(strategy=conflate::bool::overwrite_false)]
@@ -206,9 +242,29 @@ impl BackupCmd {
             })
             .collect();
 
-        let snapshot_sources = match (self.cli_sources.is_empty(), snapshot_opts.is_empty()) {
+        let mut cli_sources = self.cli_sources.clone();
+        for file in &self.files_from {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("error reading sources from {}", file.display()))?;
+            cli_sources.extend(sources_from_str(&content));
+        }
+
+        if self.split_sources {
+            if cli_sources.is_empty() {
+                bail!("split-sources needs sources given on the command line; sources from the config file already create one snapshot each.");
+            }
+            if config.backup.as_path.is_some() {
+                bail!("split-sources can't be used together with as-path!");
+            }
+        }
+
+        let snapshot_sources = match (cli_sources.is_empty(), snapshot_opts.is_empty()) {
+            (false, _) if self.split_sources => cli_sources
+                .iter()
+                .map(|source| PathList::from_iter([source]).sanitize())
+                .collect::<Result<_, _>>()?,
             (false, _) => {
-                let item = PathList::from_iter(&self.cli_sources).sanitize()?;
+                let item = PathList::from_iter(&cli_sources).sanitize()?;
                 vec![item]
             }
             (true, false) => {
@@ -287,9 +343,27 @@ impl BackupCmd {
             .no_scan(self.no_scan)
             .dry_run(config.global.dry_run);
 
-        let snap = hooks.use_with(|| -> Result<_> {
-            Ok(repo.backup(&backup_opts, &source, self.snap_opts.to_snapshot()?)?)
-        })?;
+        let mut snap = self.snap_opts.to_snapshot()?;
+        let missing = missing_tags(&snap.tags, &self.required_tags);
+        if !missing.is_empty() {
+            bail!("snapshot is missing required tags: {}", missing.join(", "));
+        }
+        if self.short_host {
+            snap.hostname =
+                short_hostname(&snap.hostname, self.snap_opts.host.is_some()).to_string();
+        }
+        if let Some(summary) = &mut snap.summary {
+            let options: Vec<_> = REDACTED_OPTIONS
+                .into_iter()
+                .chain(self.redact_options.iter().map(String::as_str))
+                .collect();
+            // redact the single arguments as the joined command can't be split correctly again
+            let args = std::env::args_os().map(|arg| arg.to_string_lossy().to_string());
+            summary.command = snapshot_command(self.snap_opts.command.as_deref(), args, &options);
+        }
+
+        let snap =
+            hooks.use_with(|| -> Result<_> { Ok(repo.backup(&backup_opts, &source, snap)?) })?;
 
         if self.json {
             let mut stdout = std::io::stdout();
@@ -326,6 +400,30 @@ impl BackupCmd {
                 summary.total_files_processed,
                 bytes_size_to_string(summary.total_bytes_processed)
             );
+            if let Some(parent) = snap.parent {
+                match repo.get_snapshots(&[parent.to_hex().to_string()]) {
+                    Ok(parents) => {
+                        if let Some(parent_summary) =
+                            parents.first().and_then(|p| p.summary.as_ref())
+                        {
+                            println!(
+                                "processed compared to parent: {} files, {}",
+                                format_change(
+                                    parent_summary.total_files_processed,
+                                    summary.total_files_processed,
+                                    |n| n.to_string()
+                                ),
+                                format_change(
+                                    parent_summary.total_bytes_processed,
+                                    summary.total_bytes_processed,
+                                    bytes_size_to_string
+                                )
+                            );
+                        }
+                    }
+                    Err(err) => warn!("could not read parent snapshot {parent}: {err}"),
+                }
+            }
             println!("snapshot {} successfully saved.", snap.id);
         }
 
@@ -333,3 +431,222 @@ impl BackupCmd {
         Ok(())
     }
 }
+
+/// Format the change from `old` to `new` with its sign
+///
+/// # Arguments
+///
+/// * `old` - The old value, e.g. from the parent snapshot
+/// * `new` - The new value
+/// * `fmt` - The function to format the absolute value of the change
+fn format_change(old: u64, new: u64, fmt: impl Fn(u64) -> String) -> String {
+    if new >= old {
+        format!("+{}", fmt(new - old))
+    } else {
+        format!("-{}", fmt(old - new))
+    }
+}
+
+/// Parse backup sources given one per line
+///
+/// Leading and trailing whitespace is removed; empty lines and lines starting with `#` are ignored.
+///
+/// # Arguments
+///
+/// * `content` - The content to parse, e.g. read from a file
+fn sources_from_str(content: &str) -> impl Iterator<Item = String> + '_ {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToString::to_string)
+}
+
+/// Command line options whose values are always redacted in the command saved in the snapshot
+const REDACTED_OPTIONS: [&str; 2] = ["--password", "--password-command"];
+
+/// Replace the values of the given options in a command by "***" and join its arguments
+///
+/// Both "--option value" and "--option=value" are redacted.
+///
+/// # Arguments
+///
+/// * `args` - The command line arguments
+/// * `options` - The options whose values should be redacted
+fn redact_command(args: impl IntoIterator<Item = String>, options: &[&str]) -> String {
+    let mut redact_next = false;
+    args.into_iter()
+        .map(|arg| {
+            if std::mem::take(&mut redact_next) {
+                return "***".to_string();
+            }
+            if let Some((option, _)) = arg
+                .split_once('=')
+                .filter(|(option, _)| options.contains(option))
+            {
+                return format!("{option}=***");
+            }
+            redact_next = options.contains(&arg.as_str());
+            arg
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Get the command to save in the snapshot
+///
+/// An explicitly given command is kept as it is; otherwise the command is built from the
+/// command line arguments with the values of the given options redacted.
+///
+/// # Arguments
+///
+/// * `explicit` - The command given by the user, if any
+/// * `args` - The command line arguments
+/// * `options` - The options whose values should be redacted
+fn snapshot_command(
+    explicit: Option<&str>,
+    args: impl IntoIterator<Item = String>,
+    options: &[&str],
+) -> String {
+    explicit.map_or_else(|| redact_command(args, options), ToString::to_string)
+}
+
+/// Get the required tags which are not contained in the given tags
+///
+/// A required tag is also fulfilled by a tag of the form "key=value" where key is the required tag.
+///
+/// # Arguments
+///
+/// * `tags` - The tags of the snapshot
+/// * `required` - The required tags
+fn missing_tags<'a>(tags: &StringList, required: &'a [String]) -> Vec<&'a str> {
+    required
+        .iter()
+        .map(String::as_str)
+        .filter(|req| {
+            !tags
+                .iter()
+                .any(|tag| tag == req || tag.split_once('=').is_some_and(|(key, _)| key == *req))
+        })
+        .collect()
+}
+
+/// Get the short host name, i.e. the host name without its domain part
+///
+/// Explicitly given host names and IP addresses are returned unchanged.
+///
+/// # Arguments
+///
+/// * `hostname` - The (possibly fully-qualified) host name
+/// * `explicit` - Whether the host name was explicitly given by the user
+fn short_hostname(hostname: &str, explicit: bool) -> &str {
+    if explicit || hostname.parse::<IpAddr>().is_ok() {
+        return hostname;
+    }
+    hostname.split('.').next().unwrap_or(hostname)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("host", false, "host")]
+    #[case("host.example.com", false, "host")]
+    #[case("host.local", false, "host")]
+    #[case("host.example.com", true, "host.example.com")]
+    #[case("192.168.1.10", false, "192.168.1.10")]
+    #[case("fe80::1", false, "fe80::1")]
+    fn short_hostname_strips_domain(
+        #[case] hostname: &str,
+        #[case] explicit: bool,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(short_hostname(hostname, explicit), expected);
+    }
+
+    #[rstest]
+    #[case(&["rustic", "backup", "/home"], "rustic backup /home")]
+    #[case(
+        &["rustic", "--password", "foo", "backup", "/home"],
+        "rustic --password *** backup /home"
+    )]
+    #[case(
+        &["rustic", "--password=foo", "--password-file", "/pw", "backup"],
+        "rustic --password=*** --password-file /pw backup"
+    )]
+    #[case(
+        &["rustic", "--password-command", "echo", "backup", "--secret", "bar"],
+        "rustic --password-command *** backup --secret ***"
+    )]
+    #[case(
+        &["rustic", "--password", "correct horse battery", "backup"],
+        "rustic --password *** backup"
+    )]
+    #[case(
+        &["rustic", "--password-command=pass show rustic", "backup", "/my path"],
+        "rustic --password-command=*** backup /my path"
+    )]
+    fn redact_command_replaces_values(#[case] args: &[&str], #[case] expected: &str) {
+        let options = REDACTED_OPTIONS
+            .into_iter()
+            .chain(["--secret"])
+            .collect::<Vec<_>>();
+        let args = args.iter().map(ToString::to_string);
+        assert_eq!(redact_command(args, &options), expected);
+    }
+
+    #[rstest]
+    #[case(None, "rustic --password *** backup")]
+    #[case(Some("my backup --password foo"), "my backup --password foo")]
+    fn snapshot_command_keeps_explicit_command(
+        #[case] explicit: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let args = ["rustic", "--password", "foo", "backup"]
+            .iter()
+            .map(ToString::to_string);
+        assert_eq!(
+            snapshot_command(explicit, args, &REDACTED_OPTIONS),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("daily,retention=long", &[], &[])]
+    #[case("daily,retention=long", &["daily", "retention"], &[])]
+    #[case("daily", &["daily", "retention"], &["retention"])]
+    #[case("retention", &["retention=long"], &["retention=long"])]
+    fn missing_tags_are_reported(
+        #[case] tags: StringList,
+        #[case] required: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let required: Vec<_> = required.iter().map(ToString::to_string).collect();
+        assert_eq!(missing_tags(&tags, &required), expected);
+    }
+
+    #[rstest]
+    #[case(10, 15, "+5")]
+    #[case(15, 10, "-5")]
+    #[case(10, 10, "+0")]
+    fn format_change_has_sign(#[case] old: u64, #[case] new: u64, #[case] expected: &str) {
+        assert_eq!(format_change(old, new, |n| n.to_string()), expected);
+    }
+
+    #[test]
+    fn format_change_uses_formatter() {
+        assert_eq!(format_change(2048, 1024, bytes_size_to_string), "-1.0 KiB");
+    }
+
+    #[test]
+    fn sources_from_str_skips_comments_and_blank_lines() {
+        let content = "# sources to backup\n/home/user\n\n  /etc  \n# /var\n/path with spaces\n";
+        assert_eq!(
+            sources_from_str(content).collect::<Vec<_>>(),
+            ["/home/user", "/etc", "/path with spaces"]
+        );
+    }
+}
@@ -2,6 +2,8 @@
 
 use crate::{status_err, Application, RUSTIC_APP};
 
+use std::io::Write;
+
 use abscissa_core::{Command, Runnable, Shutdown};
 
 use anyhow::Result;
@@ -25,7 +27,7 @@ enum CatSubCmd {
     /// Display a data blob
     DataBlob(IdOpt),
     /// Display the config file
-    Config,
+    Config(ConfigOpt),
     /// Display an index file
     Index(IdOpt),
     /// Display a snapshot file
@@ -40,6 +42,13 @@ struct IdOpt {
     id: String,
 }
 
+#[derive(Default, clap::Parser, Debug)]
+struct ConfigOpt {
+    /// Display the raw config file instead of pretty-printed JSON
+    #[clap(long)]
+    raw: bool,
+}
+
 #[derive(clap::Parser, Debug)]
 struct TreeOpts {
     /// Snapshot/path of the tree to display
@@ -60,9 +69,13 @@ impl CatCmd {
     fn inner_run(&self) -> Result<()> {
         let config = RUSTIC_APP.config();
         let data = match &self.cmd {
-            CatSubCmd::Config => config
+            CatSubCmd::Config(opt) if opt.raw => config
                 .repository
                 .run_open(|repo| Ok(repo.cat_file(FileType::Config, "")?))?,
+            // the config is already parsed when opening the repository, so just pretty-print it
+            CatSubCmd::Config(_) => config
+                .repository
+                .run_open(|repo| Ok(serde_json::to_vec_pretty(repo.config())?.into()))?,
             CatSubCmd::Index(opt) => config
                 .repository
                 .run_open(|repo| Ok(repo.cat_file(FileType::Index, &opt.id)?))?,
@@ -79,7 +92,12 @@ impl CatCmd {
                 Ok(repo.cat_tree(&opt.snap, |sn| config.snapshot_filter.matches(sn))?)
             })?,
         };
-        println!("{}", String::from_utf8(data.to_vec())?);
+        // write the data as-is without copying it; data blobs need not be valid UTF-8
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(&data)?;
+        if !matches!(self.cmd, CatSubCmd::DataBlob(_)) {
+            writeln!(stdout)?;
+        }
 
         Ok(())
     }
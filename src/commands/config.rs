@@ -5,17 +5,48 @@ use crate::{status_err, Application, RUSTIC_APP};
 use abscissa_core::{Command, Runnable, Shutdown};
 
 use anyhow::Result;
+use bytesize::ByteSize;
+use serde::Serialize;
+use serde_json::Value;
 
 use rustic_core::ConfigOptions;
 
 /// `config` subcommand
 #[derive(clap::Parser, Command, Debug)]
 pub(crate) struct ConfigCmd {
+    /// Use a predefined set of config options; explicitly given options take precedence
+    #[clap(long, value_name = "PRESET")]
+    preset: Option<ConfigPreset>,
+
     /// Config options
     #[clap(flatten)]
     config_opts: ConfigOptions,
 }
 
+/// Predefined sets of config options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ConfigPreset {
+    /// Compression level 19 and 128 MiB data packs, for data which is written once and rarely read
+    Archival,
+    /// Compression level 3 (zstd default) and default pack sizes
+    Balanced,
+    /// Compression level 1 and default pack sizes, for systems with little CPU power
+    Fast,
+}
+
+impl ConfigPreset {
+    /// The config options set by this preset
+    pub(crate) fn config_options(self) -> ConfigOptions {
+        match self {
+            Self::Archival => ConfigOptions::default()
+                .set_compression(19)
+                .set_datapack_size(ByteSize::mib(128)),
+            Self::Balanced => ConfigOptions::default().set_compression(3),
+            Self::Fast => ConfigOptions::default().set_compression(1),
+        }
+    }
+}
+
 impl Runnable for ConfigCmd {
     fn run(&self) {
         if let Err(err) = self.inner_run() {
@@ -29,16 +60,167 @@ impl ConfigCmd {
     fn inner_run(&self) -> Result<()> {
         let config = RUSTIC_APP.config();
 
-        let changed = config
-            .repository
-            .run_open(|repo| Ok(repo.apply_config(&self.config_opts)?))?;
+        // explicitly given options take precedence over the preset
+        let preset_opts = self
+            .preset
+            .map_or_else(ConfigOptions::default, ConfigPreset::config_options);
+        let config_opts = merge_config_options(preset_opts, &self.config_opts);
 
-        if changed {
-            println!("saved new config");
-        } else {
-            println!("config is unchanged");
-        }
+        config.repository.run_open(|repo| {
+            let old_config = repo.config().clone();
+            let mut new_config = old_config.clone();
+            config_opts.apply(&mut new_config)?;
+
+            let diff = config_diff(&old_config, &new_config)?;
+            if diff.is_empty() {
+                println!("config is unchanged");
+                return Ok(());
+            }
+            for (field, old, new) in &diff {
+                println!("{field}: {old} -> {new}");
+            }
+
+            if config.global.dry_run {
+                println!("would have saved new config");
+            } else {
+                _ = repo.apply_config(&config_opts)?;
+                println!("saved new config");
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Overwrite the given config options by all explicitly set options
+///
+/// # Arguments
+///
+/// * `opts` - The base options, e.g. from a preset
+/// * `explicit` - The explicitly given options which take precedence
+fn merge_config_options(mut opts: ConfigOptions, explicit: &ConfigOptions) -> ConfigOptions {
+    opts.set_compression = explicit.set_compression.or(opts.set_compression);
+    opts.set_version = explicit.set_version.or(opts.set_version);
+    opts.set_append_only = explicit.set_append_only.or(opts.set_append_only);
+    opts.set_treepack_size = explicit.set_treepack_size.or(opts.set_treepack_size);
+    opts.set_treepack_size_limit = explicit
+        .set_treepack_size_limit
+        .or(opts.set_treepack_size_limit);
+    opts.set_treepack_growfactor = explicit
+        .set_treepack_growfactor
+        .or(opts.set_treepack_growfactor);
+    opts.set_datapack_size = explicit.set_datapack_size.or(opts.set_datapack_size);
+    opts.set_datapack_size_limit = explicit
+        .set_datapack_size_limit
+        .or(opts.set_datapack_size_limit);
+    opts.set_datapack_growfactor = explicit
+        .set_datapack_growfactor
+        .or(opts.set_datapack_growfactor);
+    opts.set_min_packsize_tolerate_percent = explicit
+        .set_min_packsize_tolerate_percent
+        .or(opts.set_min_packsize_tolerate_percent);
+    opts.set_max_packsize_tolerate_percent = explicit
+        .set_max_packsize_tolerate_percent
+        .or(opts.set_max_packsize_tolerate_percent);
+    opts.set_extra_verify = explicit.set_extra_verify.or(opts.set_extra_verify);
+    opts
+}
+
+/// Compare the top-level fields of two configs
+///
+/// # Arguments
+///
+/// * `old` - The old config
+/// * `new` - The new config
+///
+/// # Returns
+///
+/// The changed fields as `(field, old value, new value)`, sorted by field name.
+/// Fields which are not set are given as `null`.
+fn config_diff(old: &impl Serialize, new: &impl Serialize) -> Result<Vec<(String, Value, Value)>> {
+    let Value::Object(mut old) = serde_json::to_value(old)? else {
+        return Ok(Vec::new());
+    };
+    let Value::Object(mut new) = serde_json::to_value(new)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut fields: Vec<_> = old.keys().chain(new.keys()).cloned().collect();
+    fields.sort_unstable();
+    fields.dedup();
+
+    Ok(fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = old.remove(&field).unwrap_or(Value::Null);
+            let new = new.remove(&field).unwrap_or(Value::Null);
+            (old != new).then_some((field, old, new))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case(ConfigPreset::Archival, Some(19), Some(ByteSize::mib(128)))]
+    #[case(ConfigPreset::Balanced, Some(3), None)]
+    #[case(ConfigPreset::Fast, Some(1), None)]
+    fn preset_sets_documented_values(
+        #[case] preset: ConfigPreset,
+        #[case] compression: Option<i32>,
+        #[case] datapack_size: Option<ByteSize>,
+    ) {
+        let opts = preset.config_options();
+        assert_eq!(opts.set_compression, compression);
+        assert_eq!(opts.set_datapack_size, datapack_size);
+        assert_eq!(opts.set_version, None);
+        assert_eq!(opts.set_treepack_size, None);
+    }
+
+    #[test]
+    fn explicit_options_take_precedence_over_preset() {
+        let explicit = ConfigOptions::default()
+            .set_compression(5)
+            .set_treepack_size(ByteSize::mib(8));
+        let opts = merge_config_options(ConfigPreset::Archival.config_options(), &explicit);
+        assert_eq!(opts.set_compression, Some(5));
+        assert_eq!(opts.set_treepack_size, Some(ByteSize::mib(8)));
+        // not explicitly given, so the preset value is kept
+        assert_eq!(opts.set_datapack_size, Some(ByteSize::mib(128)));
+    }
+
+    #[test]
+    fn config_diff_reports_compression_change() {
+        let old = json!({"version": 2, "id": "abc", "compression": 3});
+        let new = json!({"version": 2, "id": "abc", "compression": 10});
+
+        let diff = config_diff(&old, &new).unwrap();
+        assert_eq!(diff, [("compression".to_string(), json!(3), json!(10))]);
+    }
+
+    #[test]
+    fn config_diff_reports_unset_fields_as_null() {
+        let old = json!({"version": 2});
+        let new = json!({"version": 2, "min_packsize_tolerate_percent": 80});
+
+        let diff = config_diff(&old, &new).unwrap();
+        assert_eq!(
+            diff,
+            [(
+                "min_packsize_tolerate_percent".to_string(),
+                Value::Null,
+                json!(80)
+            )]
+        );
+    }
 
-        Ok(())
+    #[test]
+    fn config_diff_is_empty_for_same_config() {
+        let config = json!({"version": 2, "compression": 3});
+        assert!(config_diff(&config, &config).unwrap().is_empty());
     }
 }
@@ -1,11 +1,18 @@
 //! `dump` subcommand
 
-use std::io::{Read, Write};
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::PathBuf,
+};
 
 use crate::{repository::CliIndexedRepo, status_err, Application, RUSTIC_APP};
 
 use abscissa_core::{Command, Runnable, Shutdown};
-use anyhow::Result;
+use anyhow::{bail, Result};
+use bytesize::ByteSize;
+use clap::ValueHint;
 use log::warn;
 use rustic_core::{
     repofile::{Node, NodeType},
@@ -21,6 +28,14 @@ pub(crate) struct DumpCmd {
     #[clap(value_name = "SNAPSHOT[:PATH]")]
     snap: String,
 
+    /// Write the output to the given file instead of stdout
+    #[clap(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    output: Option<PathBuf>,
+
+    /// Split the output into parts of the given size, named FILE.000, FILE.001, ...
+    #[clap(long, value_name = "SIZE", requires = "output")]
+    split_size: Option<ByteSize>,
+
     /// Listing options
     #[clap(flatten)]
     ls_opts: LsOptions,
@@ -46,12 +61,21 @@ impl DumpCmd {
         let node =
             repo.node_from_snapshot_path(&self.snap, |sn| config.snapshot_filter.matches(sn))?;
 
-        let mut stdout = std::io::stdout();
+        let mut w: Box<dyn Write> = match (&self.output, self.split_size) {
+            (None, _) => Box::new(std::io::stdout()),
+            (Some(path), None) => Box::new(BufWriter::new(File::create(path)?)),
+            (Some(_), Some(size)) if size.as_u64() == 0 => bail!("split size must be positive!"),
+            (Some(path), Some(size)) => Box::new(SplitWriter::new(path.clone(), size.as_u64())),
+        };
         if node.is_file() {
-            repo.dump(&node, &mut stdout)?;
+            // empty files have no content to dump
+            if has_content(&node) {
+                repo.dump(&node, &mut w)?;
+            }
         } else {
-            dump_tar(&repo, &node, &mut stdout, &self.ls_opts)?;
+            dump_tar(&repo, &node, &mut w, &self.ls_opts)?;
         }
+        w.flush()?;
 
         Ok(())
     }
@@ -117,7 +141,7 @@ fn dump_tar(
             _ => {}
         }
 
-        if node.is_file() {
+        if node.is_file() && has_content(&node) {
             // write file content if this is a regular file
             let open_file = OpenFileReader {
                 repo,
@@ -135,6 +159,15 @@ fn dump_tar(
     Ok(())
 }
 
+/// Check if a file node references any content blobs
+///
+/// Empty files may be saved with an empty or without any content list.
+fn has_content(node: &Node) -> bool {
+    node.content
+        .as_ref()
+        .is_some_and(|content| !content.is_empty())
+}
+
 struct OpenFileReader<'a> {
     repo: &'a CliIndexedRepo,
     open_file: OpenFile,
@@ -153,3 +186,105 @@ impl<'a> Read for OpenFileReader<'a> {
         Ok(n)
     }
 }
+
+/// Writer which splits the output into files of a maximum size
+///
+/// The parts are named by appending `.000`, `.001`, ... to the given path.
+struct SplitWriter {
+    /// The path to which the part number is appended
+    path: PathBuf,
+    /// The maximum size of each part
+    max_size: u64,
+    /// The number of the next part
+    part: usize,
+    /// The bytes written to the current part
+    written: u64,
+    /// The current part
+    file: Option<BufWriter<File>>,
+}
+
+impl SplitWriter {
+    fn new(path: PathBuf, max_size: u64) -> Self {
+        Self {
+            path,
+            max_size,
+            part: 0,
+            written: 0,
+            file: None,
+        }
+    }
+
+    /// The path of the given part
+    fn part_path(&self, part: usize) -> PathBuf {
+        let mut path = OsString::from(&self.path);
+        path.push(format!(".{part:03}"));
+        path.into()
+    }
+}
+
+impl Write for SplitWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // start a new part if there is no current part or if it is full
+        let file = match self.file.take() {
+            Some(file) if self.written < self.max_size => file,
+            previous => {
+                if let Some(mut previous) = previous {
+                    previous.flush()?;
+                }
+                let file = BufWriter::new(File::create(self.part_path(self.part))?);
+                self.part += 1;
+                self.written = 0;
+                file
+            }
+        };
+        let file = self.file.insert(file);
+        let len = usize::try_from(self.max_size - self.written)
+            .unwrap_or(usize::MAX)
+            .min(buf.len());
+        let n = file.write(&buf[..len])?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.as_mut().map_or(Ok(()), Write::flush)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_writer_rotates_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out");
+        let data: Vec<u8> = (0..=255).cycle().take(2500).collect();
+
+        let mut w = SplitWriter::new(path.clone(), 1000);
+        w.write_all(&data[..10]).unwrap();
+        w.write_all(&data[10..]).unwrap();
+        w.flush().unwrap();
+
+        let mut parts: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        parts.sort();
+        assert_eq!(parts, [0, 1, 2].map(|part| w.part_path(part)));
+        let sizes: Vec<_> = parts
+            .iter()
+            .map(|part| std::fs::metadata(part).unwrap().len())
+            .collect();
+        assert_eq!(sizes, [1000, 1000, 500]);
+
+        let mut reassembled = Vec::new();
+        for part in parts {
+            reassembled.extend(std::fs::read(part).unwrap());
+        }
+        assert_eq!(reassembled, data);
+    }
+}
@@ -1,14 +1,18 @@
 //! `find` subcommand
 
-use std::path::{Path, PathBuf};
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
 
 use crate::{repository::CliIndexedRepo, status_err, Application, RUSTIC_APP};
 
 use abscissa_core::{Command, Runnable, Shutdown};
 use anyhow::Result;
 use clap::ValueHint;
-use globset::{Glob, GlobBuilder, GlobSetBuilder};
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
 use itertools::Itertools;
+use regex::RegexSet;
 
 use rustic_core::{
     repofile::{Node, SnapshotFile},
@@ -28,6 +32,10 @@ pub(crate) struct FindCmd {
     #[clap(long, value_name = "PATTERN", conflicts_with = "path")]
     iglob: Vec<String>,
 
+    /// regular expression matching the full path to find (can be specified multiple times)
+    #[clap(long, value_name = "REGEX", conflicts_with = "path")]
+    regex: Vec<String>,
+
     /// exact path to find
     #[clap(long, value_name = "PATH", value_hint = ValueHint::AnyPath)]
     path: Option<PathBuf>,
@@ -45,6 +53,10 @@ pub(crate) struct FindCmd {
     )]
     group_by: SnapshotGroupCriterion,
 
+    /// Only search in the latest N snapshots of each group
+    #[clap(long, value_name = "N")]
+    latest: Option<NonZeroUsize>,
+
     /// Show all snapshots instead of summarizing snapshots with identical search results
     #[clap(long)]
     all: bool,
@@ -80,6 +92,9 @@ impl FindCmd {
         })?;
         for (group, mut snapshots) in groups {
             snapshots.sort_unstable();
+            if let Some(latest) = self.latest {
+                keep_latest(&mut snapshots, latest);
+            }
             if !group.is_empty() {
                 println!("\nsearching in snapshots group {group}...");
             }
@@ -105,9 +120,8 @@ impl FindCmd {
                     _ = builder.add(GlobBuilder::new(glob).case_insensitive(true).build()?);
                 }
                 let globset = builder.build()?;
-                let matches = |path: &Path, _: &Node| {
-                    globset.is_match(path) || path.file_name().is_some_and(|f| globset.is_match(f))
-                };
+                let regexset = RegexSet::new(&self.regex)?;
+                let matches = |path: &Path, _: &Node| path_matches(&globset, &regexset, path);
                 let FindMatches {
                     paths,
                     nodes,
@@ -153,3 +167,67 @@ impl FindCmd {
         }
     }
 }
+
+/// Check if a path matches any of the globs or regular expressions
+///
+/// Globs are matched against the full path and the file name, regular expressions against the full path.
+/// Paths which are not valid UTF-8 are matched lossily.
+fn path_matches(globset: &GlobSet, regexset: &RegexSet, path: &Path) -> bool {
+    globset.is_match(path)
+        || path.file_name().is_some_and(|f| globset.is_match(f))
+        || regexset.is_match(&path.to_string_lossy())
+}
+
+/// Only keep the last `latest` items of a sorted list
+fn keep_latest<T>(items: &mut Vec<T>, latest: NonZeroUsize) {
+    let skip = items.len().saturating_sub(latest.get());
+    _ = items.drain(..skip);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&[r"^/home/.*\.txt$"], "/home/user/file.txt", true)]
+    #[case(&[r"^/home/.*\.txt$"], "/etc/file.txt", false)]
+    #[case(&[r"user/file"], "/home/user/file.txt", true)]
+    #[case(&[r"^file\.txt$"], "/home/user/file.txt", false)]
+    #[case(&[r"^/etc", r"\.txt$"], "/home/user/file.txt", true)]
+    fn path_matches_regex_against_full_path(
+        #[case] regex: &[&str],
+        #[case] path: &str,
+        #[case] expected: bool,
+    ) {
+        let globset = GlobSet::empty();
+        let regexset = RegexSet::new(regex).unwrap();
+        assert_eq!(path_matches(&globset, &regexset, Path::new(path)), expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_matches_regex_on_non_utf8_path() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let path = Path::new(OsStr::from_bytes(b"/home/caf\xe9/file.txt"));
+        let globset = GlobSet::empty();
+        let regexset = RegexSet::new([r"^/home/caf./file\.txt$"]).unwrap();
+        assert!(path_matches(&globset, &regexset, path));
+    }
+
+    #[rstest]
+    #[case(5, 2, &[4, 5])]
+    #[case(2, 5, &[1, 2])]
+    #[case(3, 3, &[1, 2, 3])]
+    fn keep_latest_truncates_oldest(
+        #[case] len: usize,
+        #[case] latest: usize,
+        #[case] expected: &[usize],
+    ) {
+        let mut items: Vec<_> = (1..=len).collect();
+        keep_latest(&mut items, NonZeroUsize::new(latest).unwrap());
+        assert_eq!(items, expected);
+    }
+}
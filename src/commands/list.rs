@@ -1,20 +1,43 @@
 //! `list` subcommand
 
-use std::num::NonZero;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZero,
+};
 
-use crate::{repository::CliOpenRepo, status_err, Application, RUSTIC_APP};
+use crate::{
+    helpers::bytes_size_to_string, repository::CliOpenRepo, status_err, Application, RUSTIC_APP,
+};
 
 use abscissa_core::{Command, Runnable, Shutdown};
 use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
 
-use rustic_core::repofile::{IndexFile, IndexId, KeyId, PackId, SnapshotId};
+use rustic_core::{
+    repofile::{BlobType, IndexBlob, IndexFile, IndexId, KeyId, PackId, SnapshotId},
+    Id, LsOptions,
+};
 
 /// `list` subcommand
 #[derive(clap::Parser, Command, Debug)]
 pub(crate) struct ListCmd {
     /// File types to list
-    #[clap(value_parser=["blobs", "indexpacks", "indexcontent", "index", "packs", "snapshots", "keys"])]
+    #[clap(value_parser=["blobs", "indexpacks", "indexcontent", "duplicateblobs", "index", "packs", "snapshots", "keys", "snapshotpacks"])]
     tpe: String,
+
+    /// Snapshot to list the packs for (type "snapshotpacks" only)
+    #[clap(value_name = "SNAPSHOT[:PATH]", default_value = "latest")]
+    snap: String,
+
+    /// Show the index content as JSON with one object per line (type "indexcontent" only)
+    ///
+    /// Each line contains the fields `blob_id`, `blob_type`, `pack_id`, `offset`, `length` and `data_length`.
+    #[clap(long)]
+    json: bool,
+
+    /// Only show the blobs with the given id or id prefix, including their offset (type "indexcontent" only)
+    #[clap(long = "blob", value_name = "ID")]
+    blob_ids: Vec<String>,
 }
 
 impl Runnable for ListCmd {
@@ -35,6 +58,8 @@ impl ListCmd {
         match self.tpe.as_str() {
             // special treatment for listing blobs: read the index and display it
             "blobs" | "indexpacks" | "indexcontent" => {
+                // the requested blob ids which have been found in the index
+                let mut found = BTreeSet::new();
                 for item in repo.stream_files::<IndexFile>()? {
                     let (_, index) = item?;
                     for pack in index.packs {
@@ -45,15 +70,41 @@ impl ListCmd {
                                 }
                             }
                             "indexcontent" => {
-                                for blob in pack.blobs {
-                                    println!(
-                                        "{:?} {:?} {:?} {} {}",
-                                        blob.tpe,
-                                        blob.id,
-                                        pack.id,
-                                        blob.length,
-                                        blob.uncompressed_length.map_or(0, NonZero::get)
-                                    );
+                                for blob in &pack.blobs {
+                                    let entry = IndexContentEntry::new(pack.id, blob);
+                                    if !self.blob_ids.is_empty() {
+                                        let matching: Vec<_> = self
+                                            .blob_ids
+                                            .iter()
+                                            .filter(|id| entry.blob_id.starts_with(id.as_str()))
+                                            .collect();
+                                        if matching.is_empty() {
+                                            continue;
+                                        }
+                                        found.extend(matching);
+                                    }
+                                    if self.json {
+                                        println!("{}", serde_json::to_string(&entry)?);
+                                    } else if self.blob_ids.is_empty() {
+                                        println!(
+                                            "{:?} {:?} {:?} {} {}",
+                                            blob.tpe,
+                                            blob.id,
+                                            pack.id,
+                                            blob.length,
+                                            blob.uncompressed_length.map_or(0, NonZero::get)
+                                        );
+                                    } else {
+                                        println!(
+                                            "{:?} {}: pack {}, offset {}, length {}, uncompressed length {}",
+                                            entry.blob_type,
+                                            entry.blob_id,
+                                            entry.pack_id,
+                                            entry.offset,
+                                            entry.length,
+                                            entry.data_length
+                                        );
+                                    }
                                 }
                             }
                             "indexpacks" => println!(
@@ -72,6 +123,62 @@ impl ListCmd {
                         }
                     }
                 }
+                if self.tpe == "indexcontent" {
+                    if let Some(id) = self.blob_ids.iter().find(|id| !found.contains(id)) {
+                        bail!("blob {id} not found in the index");
+                    }
+                }
+            }
+            // blobs contained in more than one pack; read the index and display them
+            "duplicateblobs" => {
+                let mut blobs = Vec::new();
+                for item in repo.stream_files::<IndexFile>()? {
+                    let (_, index) = item?;
+                    for pack in index.packs {
+                        blobs.extend(pack.blobs.iter().map(|blob| (blob.id, pack.id)));
+                    }
+                }
+                for (id, packs) in find_duplicates(blobs) {
+                    println!("{id:?} {packs:?}");
+                }
+            }
+            // packs needed to restore a snapshot; walk the tree and read the index
+            "snapshotpacks" => {
+                let config = RUSTIC_APP.config();
+                let repo = repo.to_indexed()?;
+                let node = repo
+                    .node_from_snapshot_path(&self.snap, |sn| config.snapshot_filter.matches(sn))?;
+
+                let mut ls_opts = LsOptions::default();
+                ls_opts.recursive = true;
+
+                let mut blobs: BTreeSet<Id> = node.subtree.iter().map(|id| **id).collect();
+                for item in repo.ls(&node, &ls_opts)? {
+                    let (_, node) = item?;
+                    blobs.extend(node.subtree.iter().map(|id| **id));
+                    blobs.extend(node.content.iter().flatten().map(|id| **id));
+                }
+
+                let mut packs = Vec::new();
+                for item in repo.stream_files::<IndexFile>()? {
+                    let (_, index) = item?;
+                    packs.extend(index.packs.into_iter().map(|pack| {
+                        let size = pack.pack_size();
+                        let ids = pack.blobs.iter().map(|blob| *blob.id).collect();
+                        (pack.id, size, ids)
+                    }));
+                }
+
+                let packs = packs_containing(packs, &blobs);
+                for (id, size) in &packs {
+                    println!("{id:?} {size}");
+                }
+                let total: u64 = packs.values().copied().map(u64::from).sum();
+                println!(
+                    "total: {} packs, {}",
+                    packs.len(),
+                    bytes_size_to_string(total)
+                );
             }
             "index" => {
                 for id in repo.list::<IndexId>()? {
@@ -101,3 +208,156 @@ impl ListCmd {
         Ok(())
     }
 }
+
+/// Entry of the index content as shown by `list indexcontent --json`
+///
+/// Every blob of the index is written as one JSON object per line. The field names and their
+/// meaning are kept stable so that the output can be used as a manifest by external tools.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct IndexContentEntry {
+    /// The id of the blob as hex string
+    blob_id: String,
+    /// The type of the blob, either "tree" or "data"
+    blob_type: BlobType,
+    /// The id of the pack containing the blob as hex string
+    pack_id: String,
+    /// The offset of the blob within the pack
+    offset: u32,
+    /// The length of the (encrypted and possibly compressed) blob within the pack
+    length: u32,
+    /// The length of the blob content after decryption and decompression
+    data_length: u32,
+}
+
+impl IndexContentEntry {
+    /// Create the entry for a blob of the given pack
+    fn new(pack_id: PackId, blob: &IndexBlob) -> Self {
+        Self {
+            blob_id: blob.id.to_hex().to_string(),
+            blob_type: blob.tpe,
+            pack_id: pack_id.to_hex().to_string(),
+            offset: blob.offset,
+            length: blob.length,
+            data_length: blob.data_length(),
+        }
+    }
+}
+
+/// Find keys which occur with more than one distinct value
+///
+/// # Arguments
+///
+/// * `items` - the key/value pairs to search, e.g. blob ids and the ids of their packs
+///
+/// # Returns
+///
+/// All keys occurring with more than one distinct value together with these values, sorted by key
+fn find_duplicates<K: Ord, V: Ord>(
+    items: impl IntoIterator<Item = (K, V)>,
+) -> Vec<(K, BTreeSet<V>)> {
+    let mut map: BTreeMap<K, BTreeSet<V>> = BTreeMap::new();
+    for (key, value) in items {
+        _ = map.entry(key).or_default().insert(value);
+    }
+    map.into_iter()
+        .filter(|(_, values)| values.len() > 1)
+        .collect()
+}
+
+/// Find the packs containing any of the given blobs
+///
+/// # Arguments
+///
+/// * `packs` - the packs to search, given as pack id, pack size and the ids of the contained blobs
+/// * `blobs` - the blobs to search for
+///
+/// # Returns
+///
+/// The ids of all packs containing at least one of the blobs together with their sizes, sorted by pack id
+fn packs_containing<P: Ord, B: Ord>(
+    packs: impl IntoIterator<Item = (P, u32, Vec<B>)>,
+    blobs: &BTreeSet<B>,
+) -> BTreeMap<P, u32> {
+    packs
+        .into_iter()
+        .filter(|(_, _, ids)| ids.iter().any(|id| blobs.contains(id)))
+        .map(|(id, size, _)| (id, size))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_duplicates_reports_blobs_in_multiple_packs() {
+        let blobs = [
+            ("blob1", "pack1"),
+            ("blob2", "pack1"),
+            ("blob3", "pack2"),
+            ("blob2", "pack3"),
+        ];
+        assert_eq!(
+            find_duplicates(blobs),
+            [("blob2", BTreeSet::from(["pack1", "pack3"]))]
+        );
+    }
+
+    #[test]
+    fn find_duplicates_is_empty_without_duplicates() {
+        let blobs = [("blob1", "pack1"), ("blob2", "pack1")];
+        assert!(find_duplicates(blobs).is_empty());
+    }
+
+    #[test]
+    fn find_duplicates_ignores_blobs_listed_twice_in_the_same_pack() {
+        let blobs = [
+            ("blob1", "pack1"),
+            ("blob1", "pack1"),
+            ("blob2", "pack2"),
+            ("blob2", "pack2"),
+            ("blob2", "pack3"),
+        ];
+        assert_eq!(
+            find_duplicates(blobs),
+            [("blob2", BTreeSet::from(["pack2", "pack3"]))]
+        );
+    }
+
+    #[test]
+    fn packs_containing_reports_each_needed_pack_once() {
+        let packs = [
+            ("pack1", 100, vec!["blob1", "blob2"]),
+            ("pack2", 200, vec!["blob3"]),
+            ("pack3", 300, vec!["blob4", "blob1"]),
+        ];
+        let blobs = BTreeSet::from(["blob1", "blob2"]);
+        assert_eq!(
+            packs_containing(packs, &blobs),
+            BTreeMap::from([("pack1", 100), ("pack3", 300)])
+        );
+    }
+
+    #[test]
+    fn index_content_entry_is_written_as_single_json_line() -> Result<()> {
+        let entry = IndexContentEntry {
+            blob_id: "01".repeat(32),
+            blob_type: BlobType::Data,
+            pack_id: "02".repeat(32),
+            offset: 100,
+            length: 60,
+            data_length: 28,
+        };
+        let line = serde_json::to_string(&entry)?;
+        assert_eq!(
+            line,
+            format!(
+                r#"{{"blob_id":"{}","blob_type":"data","pack_id":"{}","offset":100,"length":60,"data_length":28}}"#,
+                "01".repeat(32),
+                "02".repeat(32)
+            )
+        );
+        assert_eq!(serde_json::from_str::<IndexContentEntry>(&line)?, entry);
+        Ok(())
+    }
+}
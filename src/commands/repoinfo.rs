@@ -6,11 +6,16 @@ use crate::{
     status_err, Application, RUSTIC_APP,
 };
 
+use std::collections::BTreeMap;
+
 use abscissa_core::{Command, Runnable, Shutdown};
 use serde::Serialize;
 
 use anyhow::Result;
-use rustic_core::{IndexInfos, RepoFileInfo, RepoFileInfos};
+use rustic_core::{
+    repofile::{BlobType, IndexFile},
+    IndexInfos, RepoFileInfo, RepoFileInfos,
+};
 
 /// `repoinfo` subcommand
 #[derive(clap::Parser, Command, Debug)]
@@ -23,6 +28,10 @@ pub(crate) struct RepoInfoCmd {
     #[clap(long)]
     only_index: bool,
 
+    /// Show a histogram of the blob sizes (bucketed by powers of two) for each blob type
+    #[clap(long, conflicts_with = "only_files")]
+    blob_sizes: bool,
+
     /// Show infos in json format
     #[clap(long)]
     json: bool,
@@ -48,18 +57,43 @@ impl Runnable for RepoInfoCmd {
 #[derive(Serialize)]
 struct Infos {
     files: Option<RepoFileInfos>,
+    /// total size of all repository files, including the hot repository
+    total_size: Option<u64>,
     index: Option<IndexInfos>,
+    /// number of blobs per blob type and size bucket
+    blob_sizes: Option<BTreeMap<BlobType, BTreeMap<u64, u64>>>,
 }
 
 impl RepoInfoCmd {
     fn inner_run(&self, repo: CliRepo) -> Result<()> {
+        let files = (!self.only_index)
+            .then(|| -> Result<_> { Ok(repo.infos_files()?) })
+            .transpose()?;
+        let repo = (!self.only_files)
+            .then(|| -> Result<_> { Ok(repo.open()?) })
+            .transpose()?;
+        let index = repo
+            .as_ref()
+            .map(|repo| -> Result<_> { Ok(repo.infos_index()?) })
+            .transpose()?;
+        let blob_sizes = repo
+            .filter(|_| self.blob_sizes)
+            .map(|repo| -> Result<_> {
+                let mut blobs = Vec::new();
+                for item in repo.stream_files::<IndexFile>()? {
+                    let (_, index) = item?;
+                    for pack in index.packs {
+                        blobs.extend(pack.blobs.iter().map(|blob| (blob.tpe, blob.length.into())));
+                    }
+                }
+                Ok(size_histogram(blobs))
+            })
+            .transpose()?;
         let infos = Infos {
-            files: (!self.only_index)
-                .then(|| -> Result<_> { Ok(repo.infos_files()?) })
-                .transpose()?,
-            index: (!self.only_files)
-                .then(|| -> Result<_> { Ok(repo.open()?.infos_index()?) })
-                .transpose()?,
+            total_size: files.as_ref().map(total_size),
+            files,
+            index,
+            blob_sizes,
         };
 
         if self.json {
@@ -72,16 +106,62 @@ impl RepoInfoCmd {
             print_file_info("repository files", file_info.repo);
             if let Some(info) = file_info.repo_hot {
                 print_file_info("hot repository files", info);
+                if let Some(total_size) = infos.total_size {
+                    println!(
+                        "total size of repository and hot repository files: {}",
+                        bytes_size_to_string(total_size)
+                    );
+                    println!();
+                }
             }
         }
 
         if let Some(index_info) = infos.index {
             print_index_info(index_info);
         }
+
+        if let Some(blob_sizes) = infos.blob_sizes {
+            print_blob_sizes(&blob_sizes);
+        }
         Ok(())
     }
 }
 
+/// Sum up the size of all repository files, including the hot repository
+///
+/// # Arguments
+///
+/// * `infos` - the [`RepoFileInfos`] to sum up
+pub fn total_size(infos: &RepoFileInfos) -> u64 {
+    infos
+        .repo
+        .iter()
+        .chain(infos.repo_hot.iter().flatten())
+        .map(|info| info.size)
+        .sum()
+}
+
+/// Count sizes in buckets of powers of two for each key
+///
+/// A size is counted in the bucket of the smallest power of two which is greater than or equal to it.
+///
+/// # Arguments
+///
+/// * `sizes` - the sizes to count together with their key, e.g. the blob type
+pub fn size_histogram<K: Ord>(
+    sizes: impl IntoIterator<Item = (K, u64)>,
+) -> BTreeMap<K, BTreeMap<u64, u64>> {
+    let mut histogram: BTreeMap<K, BTreeMap<u64, u64>> = BTreeMap::new();
+    for (key, size) in sizes {
+        *histogram
+            .entry(key)
+            .or_default()
+            .entry(size.next_power_of_two())
+            .or_default() += 1;
+    }
+    histogram
+}
+
 /// Print infos about repository files
 ///
 /// # Arguments
@@ -189,3 +269,46 @@ pub fn print_index_info(index_info: IndexInfos) {
     println!();
     println!("{table}");
 }
+
+/// Print the histogram of blob sizes
+///
+/// # Arguments
+///
+/// * `blob_sizes` - the number of blobs per blob type and size bucket
+pub fn print_blob_sizes(blob_sizes: &BTreeMap<BlobType, BTreeMap<u64, u64>>) {
+    let mut table = table_right_from(1, ["Blob type", "Size up to", "Count"]);
+    for (blob_type, buckets) in blob_sizes {
+        for (size, count) in buckets {
+            _ = table.add_row([
+                format!("{blob_type:?}"),
+                bytes_size_to_string(*size),
+                count.to_string(),
+            ]);
+        }
+    }
+    println!();
+    println!("{table}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_histogram_counts_power_of_two_buckets() {
+        let sizes = [
+            ("data", 1),
+            ("data", 3),
+            ("data", 4),
+            ("data", 1000),
+            ("tree", 1024),
+            ("tree", 1025),
+        ];
+        let histogram = size_histogram(sizes);
+        assert_eq!(
+            histogram["data"],
+            BTreeMap::from([(1, 1), (4, 2), (1024, 1)])
+        );
+        assert_eq!(histogram["tree"], BTreeMap::from([(1024, 1), (2048, 1)]));
+    }
+}
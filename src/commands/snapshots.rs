@@ -6,6 +6,8 @@ use crate::{
     status_err, Application, RUSTIC_APP,
 };
 
+use std::collections::BTreeMap;
+
 use abscissa_core::{Command, Runnable, Shutdown};
 use anyhow::Result;
 use comfy_table::Cell;
@@ -48,6 +50,10 @@ pub(crate) struct SnapshotCmd {
     #[clap(long, conflicts_with_all = &["long", "json"])]
     all: bool,
 
+    /// Show all tags used by the snapshots together with the number of snapshots having them
+    #[clap(long, conflicts_with_all = &["long", "all"])]
+    tag_counts: bool,
+
     #[cfg(feature = "tui")]
     /// Run in interactive UI mode
     #[clap(long, short)]
@@ -80,6 +86,25 @@ impl SnapshotCmd {
             config.snapshot_filter.matches(sn)
         })?;
 
+        if self.tag_counts {
+            let counts = tag_histogram(groups.iter().flat_map(|(_, snaps)| snaps));
+            if self.json {
+                let mut stdout = std::io::stdout();
+                serde_json::to_writer_pretty(&mut stdout, &counts)?;
+            } else {
+                let mut table = table_right_from(1, ["Tag", "Snapshots"]);
+                _ = table.add_rows(
+                    counts
+                        .iter()
+                        .map(|(tag, count)| [tag.clone(), count.to_string()]),
+                );
+                println!("{table}");
+                println!();
+                println!("total: {} tag(s)", counts.len());
+            }
+            return Ok(());
+        }
+
         if self.json {
             let mut stdout = std::io::stdout();
             serde_json::to_writer_pretty(&mut stdout, &groups)?;
@@ -139,6 +164,25 @@ impl SnapshotCmd {
     }
 }
 
+/// Count for each tag the number of snapshots having this tag
+///
+/// # Arguments
+///
+/// * `snapshots` - The snapshots to count the tags of
+///
+/// # Returns
+///
+/// A map from each tag to the number of snapshots having it, sorted by tag
+pub fn tag_histogram<'a>(
+    snapshots: impl IntoIterator<Item = &'a SnapshotFile>,
+) -> BTreeMap<String, u64> {
+    let mut counts = BTreeMap::new();
+    for tag in snapshots.into_iter().flat_map(|sn| sn.tags.iter()) {
+        *counts.entry(tag.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
 pub fn snap_to_table(sn: &SnapshotFile, count: usize) -> [String; 9] {
     let tags = sn.tags.formatln();
     let paths = sn.paths.formatln();
@@ -251,3 +295,34 @@ pub fn fill_table(snap: &SnapshotFile, mut add_entry: impl FnMut(&str, String))
         add_entry("Description", description.clone());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+
+    use rustic_core::StringList;
+
+    #[test]
+    fn tag_histogram_counts_overlapping_tags() {
+        let mut snaps: Vec<_> = ["daily,important", "daily", "weekly,important"]
+            .into_iter()
+            .map(|tags| SnapshotFile {
+                tags: StringList::from_str(tags).unwrap(),
+                ..Default::default()
+            })
+            .collect();
+        snaps.push(SnapshotFile::default());
+
+        let counts = tag_histogram(&snaps);
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            [
+                ("daily".to_string(), 2),
+                ("important".to_string(), 2),
+                ("weekly".to_string(), 1),
+            ]
+        );
+    }
+}
@@ -4,10 +4,13 @@ use crate::{repository::CliOpenRepo, status_err, Application, RUSTIC_APP};
 
 use abscissa_core::{Command, Runnable, Shutdown};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use chrono::{Duration, Local};
 
-use rustic_core::{repofile::DeleteOption, StringList};
+use rustic_core::{
+    repofile::{DeleteOption, SnapshotFile},
+    StringList,
+};
 
 /// `tag` subcommand
 #[derive(clap::Parser, Command, Debug)]
@@ -58,6 +61,78 @@ pub(crate) struct TagCmd {
     /// Mark snapshot to be deleted after given duration (e.g. 10d)
     #[clap(long, value_name = "DURATION", help_heading = "Delete mark options")]
     set_delete_after: Option<humantime::Duration>,
+
+    /// Set the description, replacing the existing one
+    #[clap(
+        long,
+        value_name = "DESCRIPTION",
+        conflicts_with_all = &["append_description", "remove_description"],
+        help_heading = "Description options"
+    )]
+    set_description: Option<String>,
+
+    /// Append a line to the description
+    #[clap(
+        long,
+        value_name = "DESCRIPTION",
+        conflicts_with = "remove_description",
+        help_heading = "Description options"
+    )]
+    append_description: Option<String>,
+
+    /// Remove the description
+    #[clap(long, help_heading = "Description options")]
+    remove_description: bool,
+
+    /// Set the host name, e.g. after migrating to a new machine
+    #[clap(long, value_name = "HOSTNAME", help_heading = "Metadata options")]
+    set_host: Option<String>,
+
+    /// Set the label
+    #[clap(long, value_name = "LABEL", help_heading = "Metadata options")]
+    set_label: Option<String>,
+
+    /// Set the user name
+    #[clap(long, value_name = "USERNAME", help_heading = "Metadata options")]
+    set_username: Option<String>,
+
+    /// Set the backup paths, e.g. after moving the backed up data
+    #[clap(
+        long,
+        value_name = "PATH[,PATH,..]",
+        value_parser = parse_paths,
+        help_heading = "Metadata options"
+    )]
+    set_paths: Option<StringList>,
+}
+
+/// A change of the snapshot description
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DescriptionChange<'a> {
+    /// Replace the description
+    Set(&'a str),
+    /// Append a line to the description
+    Append(&'a str),
+    /// Remove the description
+    Remove,
+}
+
+impl DescriptionChange<'_> {
+    /// Apply the change to the given snapshot
+    ///
+    /// # Returns
+    ///
+    /// `true` if the description of the snapshot has been changed
+    fn apply(self, sn: &mut SnapshotFile) -> bool {
+        let description = match (self, &sn.description) {
+            (Self::Append(desc), Some(old)) if !old.is_empty() => Some(format!("{old}\n{desc}")),
+            (Self::Set(desc) | Self::Append(desc), _) => Some(desc.to_string()),
+            (Self::Remove, _) => None,
+        };
+        let changed = sn.description != description;
+        sn.description = description;
+        changed
+    }
 }
 
 impl Runnable for TagCmd {
@@ -94,10 +169,37 @@ impl TagCmd {
             (false, false, None) => None,
         };
 
+        let description = match (
+            self.remove_description,
+            &self.set_description,
+            &self.append_description,
+        ) {
+            (true, _, _) => Some(DescriptionChange::Remove),
+            (_, Some(desc), _) => Some(DescriptionChange::Set(desc)),
+            (_, _, Some(desc)) => Some(DescriptionChange::Append(desc)),
+            (false, None, None) => None,
+        };
+
+        // Note: changed snapshots are saved under a new id; `original` still refers to the
+        // id of the snapshot as it was originally created.
         let snapshots: Vec<_> = snapshots
             .into_iter()
             .filter_map(|mut sn| {
+                let mut changed = description.is_some_and(|change| change.apply(&mut sn));
+                if let Some(host) = &self.set_host {
+                    changed |= set_if_changed(&mut sn.hostname, host);
+                }
+                if let Some(label) = &self.set_label {
+                    changed |= set_if_changed(&mut sn.label, label);
+                }
+                if let Some(username) = &self.set_username {
+                    changed |= set_if_changed(&mut sn.username, username);
+                }
+                if let Some(paths) = &self.set_paths {
+                    changed |= set_if_changed(&mut sn.paths, paths);
+                }
                 sn.modify_sn(self.set.clone(), self.add.clone(), &self.remove, &delete)
+                    .or_else(|| changed.then_some(sn))
             })
             .collect();
         let old_snap_ids: Vec<_> = snapshots.iter().map(|sn| sn.id).collect();
@@ -116,3 +218,91 @@ impl TagCmd {
         Ok(())
     }
 }
+
+/// Set a snapshot field to the given value
+///
+/// # Returns
+///
+/// `true` if the field has been changed
+fn set_if_changed<T: PartialEq + Clone>(field: &mut T, value: &T) -> bool {
+    if field == value {
+        return false;
+    }
+    value.clone_into(field);
+    true
+}
+
+/// Parse a list of backup paths
+///
+/// # Errors
+///
+/// If the list or any of the paths is empty
+fn parse_paths(s: &str) -> Result<StringList> {
+    let paths: StringList = s.parse()?;
+    if paths.iter().next().is_none() || paths.iter().any(|path| path.trim().is_empty()) {
+        bail!("invalid path list \"{s}\": paths must not be empty");
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(None, DescriptionChange::Append("new"), Some("new"), true)]
+    #[case(Some("old"), DescriptionChange::Append("new"), Some("old\nnew"), true)]
+    #[case(Some("old"), DescriptionChange::Set("new"), Some("new"), true)]
+    #[case(Some("old"), DescriptionChange::Set("old"), Some("old"), false)]
+    #[case(Some("old"), DescriptionChange::Remove, None, true)]
+    #[case(None, DescriptionChange::Remove, None, false)]
+    fn description_change_apply(
+        #[case] old: Option<&str>,
+        #[case] change: DescriptionChange<'_>,
+        #[case] expected: Option<&str>,
+        #[case] changed: bool,
+    ) {
+        let mut sn = SnapshotFile {
+            description: old.map(ToString::to_string),
+            ..Default::default()
+        };
+        assert_eq!(change.apply(&mut sn), changed);
+        assert_eq!(sn.description.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("old-host", "new-host", true)]
+    #[case("old-host", "old-host", false)]
+    fn set_hostname(#[case] old: &str, #[case] new: &str, #[case] changed: bool) {
+        let mut sn = SnapshotFile {
+            hostname: old.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(set_if_changed(&mut sn.hostname, &new.to_string()), changed);
+        assert_eq!(sn.hostname, new);
+    }
+
+    #[rstest]
+    #[case("/home", true)]
+    #[case("/home,/etc", true)]
+    #[case("", false)]
+    #[case("/home,,/etc", false)]
+    #[case("/home, ", false)]
+    fn parse_paths_validates(#[case] input: &str, #[case] valid: bool) {
+        assert_eq!(parse_paths(input).is_ok(), valid);
+    }
+
+    #[test]
+    fn set_paths() {
+        let mut sn = SnapshotFile {
+            paths: "/old".parse().unwrap(),
+            ..Default::default()
+        };
+        let paths = parse_paths("/new,/other").unwrap();
+        assert!(set_if_changed(&mut sn.paths, &paths));
+        assert_eq!(sn.paths, paths);
+        assert!(!set_if_changed(&mut sn.paths, &paths));
+    }
+}
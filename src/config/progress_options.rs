@@ -1,8 +1,14 @@
 //! Progress Bar Config
 
-use std::{borrow::Cow, fmt::Write, time::Duration};
+use std::{
+    borrow::Cow,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use indicatif::{HumanDuration, ProgressBar, ProgressState, ProgressStyle};
+use log::info;
 
 use clap::Parser;
 use conflate::Merge;
@@ -12,6 +18,11 @@ use serde_with::{serde_as, DisplayFromStr};
 
 use rustic_core::{Progress, ProgressBars};
 
+use crate::helpers::bytes_size_to_string;
+
+/// Default interval to log progress, if progress logging is enabled
+const DEFAULT_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Progress Bar Config
 #[serde_as]
 #[derive(Default, Debug, Parser, Clone, Copy, Deserialize, Serialize, Merge)]
@@ -22,7 +33,17 @@ pub struct ProgressOptions {
     #[merge(strategy=conflate::bool::overwrite_false)]
     pub no_progress: bool,
 
-    /// Interval to update progress bars
+    /// Regularly log the progress instead of showing progress bars (e.g. for non-interactive runs)
+    #[clap(
+        long,
+        global = true,
+        env = "RUSTIC_PROGRESS_LOG",
+        conflicts_with = "no_progress"
+    )]
+    #[merge(strategy=conflate::bool::overwrite_false)]
+    pub progress_log: bool,
+
+    /// Interval to update progress bars or to log the progress [default: 10s when logging]
     #[clap(
         long,
         global = true,
@@ -47,7 +68,25 @@ impl ProgressOptions {
 
     /// Create a hidden progress bar
     pub fn no_progress() -> RusticProgress {
-        RusticProgress(ProgressBar::hidden(), ProgressType::Hidden)
+        RusticProgress(ProgressBar::hidden(), ProgressType::Hidden, None)
+    }
+
+    /// Create a progress which is regularly logged instead of being shown as progress bar
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the progress
+    /// * `tpe` - The type of the progress
+    fn log_progress(
+        &self,
+        prefix: impl Into<Cow<'static, str>>,
+        tpe: ProgressType,
+    ) -> RusticProgress {
+        let p = ProgressBar::hidden();
+        p.set_prefix(prefix);
+        info!("{}", p.prefix());
+        let interval = self.progress_interval.map_or(DEFAULT_LOG_INTERVAL, |i| *i);
+        RusticProgress(p, tpe, Some(Arc::new(ProgressLogger::new(interval))))
     }
 }
 
@@ -58,6 +97,9 @@ impl ProgressBars for ProgressOptions {
         if self.no_progress {
             return Self::no_progress();
         }
+        if self.progress_log {
+            return self.log_progress(prefix, ProgressType::Spinner);
+        }
         let p = ProgressBar::new(0).with_style(
             ProgressStyle::default_bar()
                 .template("[{elapsed_precise}] {prefix:30} {spinner}")
@@ -65,13 +107,16 @@ impl ProgressBars for ProgressOptions {
         );
         p.set_prefix(prefix);
         p.enable_steady_tick(self.progress_interval());
-        RusticProgress(p, ProgressType::Spinner)
+        RusticProgress(p, ProgressType::Spinner, None)
     }
 
     fn progress_counter(&self, prefix: impl Into<Cow<'static, str>>) -> RusticProgress {
         if self.no_progress {
             return Self::no_progress();
         }
+        if self.progress_log {
+            return self.log_progress(prefix, ProgressType::Counter);
+        }
         let p = ProgressBar::new(0).with_style(
             ProgressStyle::default_bar()
                 .template("[{elapsed_precise}] {prefix:30} {bar:40.cyan/blue} {pos:>10}")
@@ -79,7 +124,7 @@ impl ProgressBars for ProgressOptions {
         );
         p.set_prefix(prefix);
         p.enable_steady_tick(self.progress_interval());
-        RusticProgress(p, ProgressType::Counter)
+        RusticProgress(p, ProgressType::Counter, None)
     }
 
     fn progress_hidden(&self) -> RusticProgress {
@@ -90,6 +135,9 @@ impl ProgressBars for ProgressOptions {
         if self.no_progress {
             return Self::no_progress();
         }
+        if self.progress_log {
+            return self.log_progress(prefix, ProgressType::Bytes);
+        }
         let p = ProgressBar::new(0).with_style(
             ProgressStyle::default_bar()
             .template("[{elapsed_precise}] {prefix:30} {bar:40.cyan/blue} {bytes:>10}            {bytes_per_sec:12}")
@@ -97,7 +145,7 @@ impl ProgressBars for ProgressOptions {
             );
         p.set_prefix(prefix);
         p.enable_steady_tick(self.progress_interval());
-        RusticProgress(p, ProgressType::Bytes)
+        RusticProgress(p, ProgressType::Bytes, None)
     }
 }
 
@@ -109,13 +157,78 @@ enum ProgressType {
     Bytes,
 }
 
+/// Logger for progress which is logged instead of being shown as progress bar
+#[derive(Debug)]
+struct ProgressLogger {
+    /// The interval to log the progress
+    interval: Duration,
+    /// The time of the last log
+    last_log: Mutex<Instant>,
+}
+
+impl ProgressLogger {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_log: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Check if the progress should be logged and reset the interval if so
+    fn is_due(&self) -> bool {
+        let mut last_log = self.last_log.lock().unwrap();
+        if last_log.elapsed() < self.interval {
+            return false;
+        }
+        *last_log = Instant::now();
+        true
+    }
+}
+
+/// Format a progress message for logging
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the progress
+/// * `tpe` - The type of the progress
+/// * `pos` - The current position
+/// * `len` - The length, if known
+fn progress_message(prefix: &str, tpe: &ProgressType, pos: u64, len: Option<u64>) -> String {
+    let fmt_num = |n| match tpe {
+        ProgressType::Bytes => bytes_size_to_string(n),
+        _ => n.to_string(),
+    };
+    match (tpe, len) {
+        (ProgressType::Hidden | ProgressType::Spinner, _) => prefix.to_string(),
+        (_, Some(len)) if len > 0 => format!(
+            "{prefix} {} / {} ({}%)",
+            fmt_num(pos),
+            fmt_num(len),
+            pos.min(len) * 100 / len
+        ),
+        (_, _) => format!("{prefix} {}", fmt_num(pos)),
+    }
+}
+
 /// A default progress bar
 #[derive(Debug, Clone)]
-pub struct RusticProgress(ProgressBar, ProgressType);
+pub struct RusticProgress(ProgressBar, ProgressType, Option<Arc<ProgressLogger>>);
+
+impl RusticProgress {
+    /// The current progress formatted for logging
+    fn message(&self) -> String {
+        progress_message(
+            &self.0.prefix(),
+            &self.1,
+            self.0.position(),
+            self.0.length(),
+        )
+    }
+}
 
 impl Progress for RusticProgress {
     fn is_hidden(&self) -> bool {
-        self.0.is_hidden()
+        self.2.is_none() && self.0.is_hidden()
     }
 
     fn set_length(&self, len: u64) {
@@ -154,9 +267,88 @@ impl Progress for RusticProgress {
 
     fn inc(&self, inc: u64) {
         self.0.inc(inc);
+        if self.2.as_ref().is_some_and(|logger| logger.is_due()) {
+            info!("{}", self.message());
+        }
     }
 
     fn finish(&self) {
+        if self.2.is_some() {
+            info!("{} done", self.message());
+        }
         self.0.finish_with_message("done");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(ProgressType::Spinner, 5, None, "reading")]
+    #[case(ProgressType::Counter, 5, None, "reading 5")]
+    #[case(ProgressType::Counter, 5, Some(0), "reading 5")]
+    #[case(ProgressType::Counter, 5, Some(20), "reading 5 / 20 (25%)")]
+    #[case(ProgressType::Bytes, 512, Some(1024), "reading 512 B / 1.0 KiB (50%)")]
+    fn progress_message_is_formatted(
+        #[case] tpe: ProgressType,
+        #[case] pos: u64,
+        #[case] len: Option<u64>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(progress_message("reading", &tpe, pos, len), expected);
+    }
+
+    /// Logger capturing all log messages
+    struct CaptureLogger(Mutex<Vec<String>>);
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+
+    #[test]
+    fn log_progress_logs_progress() {
+        _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Info);
+
+        let opts = ProgressOptions {
+            progress_log: true,
+            progress_interval: Some(Duration::ZERO.into()),
+            ..Default::default()
+        };
+        let p = opts.progress_counter("log-progress-test");
+        p.set_length(10);
+        p.inc(5);
+        p.finish();
+
+        // other tests may log concurrently
+        let logs: Vec<_> = LOGGER
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|msg| msg.starts_with("log-progress-test"))
+            .cloned()
+            .collect();
+        assert_eq!(
+            logs,
+            [
+                "log-progress-test",
+                "log-progress-test 5 / 10 (50%)",
+                "log-progress-test 5 / 10 (50%) done"
+            ]
+        );
+    }
+}
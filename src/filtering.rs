@@ -5,6 +5,7 @@ use derive_more::derive::Display;
 use log::warn;
 use rustic_core::{repofile::SnapshotFile, StringList};
 use std::{
+    borrow::Cow,
     error::Error,
     fmt::{Debug, Display},
     str::FromStr,
@@ -129,6 +130,11 @@ pub struct SnapshotFilter {
     #[serde_as(as = "Option<DisplayFromStr>")]
     #[merge(strategy=conflate::option::overwrite_none)]
     filter_fn: Option<String>,
+
+    /// Match hostnames, labels and tags case-insensitively
+    #[clap(long, global = true)]
+    #[merge(strategy=conflate::bool::overwrite_false)]
+    filter_ignore_case: bool,
 }
 
 impl SnapshotFilter {
@@ -170,23 +176,78 @@ impl SnapshotFilter {
             return false;
         }
 
+        // hostname, label and tags are normalized once if matching ignores the case
+        let ignore_case = self.filter_ignore_case;
+        let hostname = normalize(&snapshot.hostname, ignore_case);
+        let label = normalize(&snapshot.label, ignore_case);
+        let tags: Vec<_> = snapshot
+            .tags
+            .iter()
+            .map(|tag| normalize(tag, ignore_case))
+            .collect();
+
         // For the the `Vec`s we have two possibilities:
         // - There exists a suitable matches method on the snapshot item
         //   (this automatically handles empty filter correctly):
         snapshot.paths.matches(&self.filter_paths)
-            && snapshot.tags.matches(&self.filter_tags)
         //  - manually check if the snapshot item is contained in the `Vec`
         //    but only if the `Vec` is not empty.
         //    If it is empty, no condition is given.
             && (self.filter_paths_exact.is_empty()
                 || self.filter_paths_exact.contains(&snapshot.paths))
+            && (self.filter_tags.is_empty()
+                || self
+                    .filter_tags
+                    .iter()
+                    .any(|filter| contains_all(&tags, filter, ignore_case)))
             && (self.filter_tags_exact.is_empty()
-                || self.filter_tags_exact.contains(&snapshot.tags))
-            && (self.filter_hosts.is_empty() || self.filter_hosts.contains(&snapshot.hostname))
-            && (self.filter_labels.is_empty() || self.filter_labels.contains(&snapshot.label))
+                || if ignore_case {
+                    self.filter_tags_exact.iter().any(|filter| {
+                        let filter: Vec<_> = filter
+                            .iter()
+                            .map(|tag| normalize(tag, ignore_case))
+                            .collect();
+                        filter.iter().all(|tag| tags.contains(tag))
+                            && tags.iter().all(|tag| filter.contains(tag))
+                    })
+                } else {
+                    self.filter_tags_exact.contains(&snapshot.tags)
+                })
+            && (self.filter_hosts.is_empty()
+                || self
+                    .filter_hosts
+                    .iter()
+                    .any(|host| normalize(host, ignore_case) == hostname))
+            && (self.filter_labels.is_empty()
+                || self
+                    .filter_labels
+                    .iter()
+                    .any(|l| normalize(l, ignore_case) == label))
     }
 }
 
+/// Normalize a string for comparison, i.e. lowercase it if the case should be ignored
+fn normalize(s: &str, ignore_case: bool) -> Cow<'_, str> {
+    if ignore_case {
+        Cow::Owned(s.to_lowercase())
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Check if `list` contains all items of `items`
+///
+/// # Arguments
+///
+/// * `list` - The normalized list to search in
+/// * `items` - The items which must all be contained in `list`
+/// * `ignore_case` - Whether to ignore the case of the items
+fn contains_all(list: &[Cow<'_, str>], items: &StringList, ignore_case: bool) -> bool {
+    items
+        .iter()
+        .all(|item| list.contains(&normalize(item, ignore_case)))
+}
+
 #[derive(Debug, Clone, Display)]
 struct AfterDate(DateTime<Local>);
 
@@ -293,4 +354,71 @@ mod tests {
         assert_eq!(input.from.map(|v| v.0), from);
         assert_eq!(input.to.map(|v| v.0), to);
     }
+
+    #[rstest]
+    #[case("Daily", false, false)]
+    #[case("Daily", true, true)]
+    #[case("DAILY,Important", true, true)]
+    #[case("daily,other", true, false)]
+    fn filter_tags_ignore_case(
+        #[case] filter_tags: StringList,
+        #[case] ignore_case: bool,
+        #[case] expected: bool,
+    ) {
+        let snapshot = SnapshotFile {
+            tags: StringList::from_str("daily,important").unwrap(),
+            ..Default::default()
+        };
+        let filter = SnapshotFilter {
+            filter_tags: vec![filter_tags],
+            filter_ignore_case: ignore_case,
+            ..Default::default()
+        };
+        assert_eq!(filter.matches(&snapshot), expected);
+    }
+
+    #[rstest]
+    #[case("daily,important", false, true)]
+    #[case("daily", false, false)]
+    #[case("Daily,Important", false, false)]
+    #[case("Daily,Important", true, true)]
+    #[case("daily", true, false)]
+    #[case("daily,important,other", true, false)]
+    fn filter_tags_exact_ignore_case(
+        #[case] filter_tags: StringList,
+        #[case] ignore_case: bool,
+        #[case] expected: bool,
+    ) {
+        let snapshot = SnapshotFile {
+            tags: StringList::from_str("daily,important").unwrap(),
+            ..Default::default()
+        };
+        let filter = SnapshotFilter {
+            filter_tags_exact: vec![filter_tags],
+            filter_ignore_case: ignore_case,
+            ..Default::default()
+        };
+        assert_eq!(filter.matches(&snapshot), expected);
+    }
+
+    #[rstest]
+    #[case("Host1", false, false)]
+    #[case("Host1", true, true)]
+    #[case("host2", true, false)]
+    fn filter_hosts_ignore_case(
+        #[case] filter_host: String,
+        #[case] ignore_case: bool,
+        #[case] expected: bool,
+    ) {
+        let snapshot = SnapshotFile {
+            hostname: "host1".to_string(),
+            ..Default::default()
+        };
+        let filter = SnapshotFilter {
+            filter_hosts: vec![filter_host],
+            filter_ignore_case: ignore_case,
+            ..Default::default()
+        };
+        assert_eq!(filter.matches(&snapshot), expected);
+    }
 }
@@ -55,6 +55,7 @@ fn test_backup_and_check_passes() -> TestResult<()> {
             .arg(backup)
             .assert()
             .success()
+            .stdout(predicate::str::contains("compared to parent").not())
             .stdout(predicate::str::contains("successfully saved."));
     }
 
@@ -75,6 +76,9 @@ fn test_backup_and_check_passes() -> TestResult<()> {
             .assert()
             .success()
             .stdout(predicate::str::contains("Added to the repo: 0 B"))
+            .stdout(predicate::str::contains(
+                "processed compared to parent: +0 files, +0 B",
+            ))
             .stdout(predicate::str::contains("successfully saved."));
     }
 
@@ -138,3 +142,284 @@ fn test_backup_and_restore_passes() -> TestResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_backup_and_dump_empty_file_passes() -> TestResult<()> {
+    let temp_dir = setup()?;
+    let source_dir = temp_dir.path().join("source");
+    std::fs::create_dir(&source_dir)?;
+    std::fs::File::create(source_dir.join("empty"))?;
+
+    {
+        // Run `backup` with an empty file
+        rustic_runner(&temp_dir)?
+            .arg("backup")
+            .arg(&source_dir)
+            .args(["--as-path", "/source"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("successfully saved."));
+    }
+    {
+        // Run `dump` on the empty file
+        rustic_runner(&temp_dir)?
+            .args(["dump", "latest:/source/empty"])
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_and_dump_to_split_files_passes() -> TestResult<()> {
+    let temp_dir = setup()?;
+    let source_dir = temp_dir.path().join("source");
+    std::fs::create_dir(&source_dir)?;
+    let content: Vec<u8> = (0..=255).cycle().take(2500).collect();
+    std::fs::write(source_dir.join("file"), &content)?;
+
+    {
+        // Run `backup`
+        rustic_runner(&temp_dir)?
+            .arg("backup")
+            .arg(&source_dir)
+            .args(["--as-path", "/source"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("successfully saved."));
+    }
+    {
+        // Run `dump` into a single file
+        let output = temp_dir.path().join("dump");
+        rustic_runner(&temp_dir)?
+            .args(["dump", "latest:/source/file", "--output"])
+            .arg(&output)
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+        assert_eq!(std::fs::read(&output)?, content);
+    }
+    {
+        // Run `dump` into parts of 1000 bytes
+        let output = temp_dir.path().join("split");
+        rustic_runner(&temp_dir)?
+            .args([
+                "dump",
+                "latest:/source/file",
+                "--split-size",
+                "1000",
+                "--output",
+            ])
+            .arg(&output)
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+
+        let mut dumped = Vec::new();
+        for (part, size) in [("split.000", 1000), ("split.001", 1000), ("split.002", 500)] {
+            let data = std::fs::read(temp_dir.path().join(part))?;
+            assert_eq!(data.len(), size);
+            dumped.extend(data);
+        }
+        assert!(!temp_dir.path().join("split.003").exists());
+        assert_eq!(dumped, content);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_split_sources_passes() -> TestResult<()> {
+    let temp_dir = setup()?;
+
+    {
+        // Run `backup` with two sources, creating one snapshot per source
+        rustic_runner(&temp_dir)?
+            .args(["backup", "--split-sources", "src/", "tests/"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("successfully saved.").count(2));
+    }
+    {
+        // Run `snapshots`
+        rustic_runner(&temp_dir)?
+            .arg("snapshots")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("total: 2 snapshot(s)"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_split_sources_with_as_path_fails() -> TestResult<()> {
+    let temp_dir = setup()?;
+
+    // all split snapshots would be saved under the same path
+    rustic_runner(&temp_dir)?
+        .args([
+            "backup",
+            "--split-sources",
+            "--as-path",
+            "/x",
+            "src/",
+            "tests/",
+        ])
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_and_list_snapshotpacks_passes() -> TestResult<()> {
+    let temp_dir = setup()?;
+    let source_dir = temp_dir.path().join("source");
+    std::fs::create_dir(&source_dir)?;
+    std::fs::write(source_dir.join("file"), "content")?;
+
+    {
+        // Run `backup`
+        rustic_runner(&temp_dir)?
+            .arg("backup")
+            .arg(&source_dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("successfully saved."));
+    }
+    {
+        // Run `list snapshotpacks`; the snapshot needs one tree pack and one data pack
+        rustic_runner(&temp_dir)?
+            .args(["list", "snapshotpacks", "latest"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("total: 2 packs"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_and_list_indexcontent_json_passes() -> TestResult<()> {
+    let temp_dir = setup()?;
+    let source_dir = temp_dir.path().join("source");
+    std::fs::create_dir(&source_dir)?;
+    std::fs::write(source_dir.join("file"), "content")?;
+
+    {
+        // Run `backup`
+        rustic_runner(&temp_dir)?
+            .arg("backup")
+            .arg(&source_dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("successfully saved."));
+    }
+    {
+        // Run `list indexcontent --json` and parse the manifest again
+        let output = rustic_runner(&temp_dir)?
+            .args(["list", "indexcontent", "--json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+
+        let mut blob_types = Vec::new();
+        for line in String::from_utf8(output)?.lines() {
+            let entry: serde_json::Value = serde_json::from_str(line)?;
+            assert_eq!(entry["blob_id"].as_str().map(str::len), Some(64));
+            assert_eq!(entry["pack_id"].as_str().map(str::len), Some(64));
+            assert!(entry["offset"].is_u64());
+            assert!(entry["length"].is_u64());
+            assert!(entry["data_length"].is_u64());
+            blob_types.push(entry["blob_type"].as_str().unwrap_or_default().to_string());
+        }
+        assert!(blob_types.iter().any(|tpe| tpe == "tree"));
+        assert!(blob_types.iter().any(|tpe| tpe == "data"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_and_list_indexcontent_for_blob_passes() -> TestResult<()> {
+    let temp_dir = setup()?;
+    let source_dir = temp_dir.path().join("source");
+    std::fs::create_dir(&source_dir)?;
+    std::fs::write(source_dir.join("file"), "content")?;
+
+    {
+        // Run `backup`
+        rustic_runner(&temp_dir)?
+            .arg("backup")
+            .arg(&source_dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("successfully saved."));
+    }
+
+    // Take a blob from the index manifest
+    let output = rustic_runner(&temp_dir)?
+        .args(["list", "indexcontent", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let line = String::from_utf8(output)?
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let entry: serde_json::Value = serde_json::from_str(&line)?;
+    let blob_id = entry["blob_id"].as_str().unwrap_or_default();
+
+    {
+        // Run `list indexcontent` for a known blob
+        rustic_runner(&temp_dir)?
+            .args(["list", "indexcontent", "--blob", blob_id])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!(
+                "{blob_id}: pack {}, offset {}, length {}, uncompressed length {}",
+                entry["pack_id"].as_str().unwrap_or_default(),
+                entry["offset"],
+                entry["length"],
+                entry["data_length"]
+            )));
+    }
+    {
+        // Run `list indexcontent` for an unknown blob
+        let unknown = "0".repeat(64);
+        rustic_runner(&temp_dir)?
+            .args(["list", "indexcontent", "--blob", &unknown])
+            .assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::str::contains(format!(
+                "blob {unknown} not found in the index"
+            )));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_backup_with_explicit_command_passes() -> TestResult<()> {
+    let temp_dir = setup()?;
+
+    {
+        // Run `backup` with an explicitly given command
+        rustic_runner(&temp_dir)?
+            .args(["backup", "--json", "--command", "my backup job", "src/"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(r#""command": "my backup job""#));
+    }
+
+    Ok(())
+}
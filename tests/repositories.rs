@@ -113,6 +113,49 @@ fn test_rustic_repo_passes(rustic_repo: Result<TestSource>) -> TestResult<()> {
     Ok(())
 }
 
+/// Sum up the sizes of all files within the given directory and its subdirectories
+fn dir_size(path: &Path) -> Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        size += if meta.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            meta.len()
+        };
+    }
+    Ok(size)
+}
+
+#[rstest]
+fn test_repoinfo_total_size_passes(rustic_repo: Result<TestSource>) -> TestResult<()> {
+    let rustic_repo = rustic_repo?;
+    let repo_password = "rustic";
+    let rustic_repo_path = rustic_repo.into_path();
+    let rustic_repo_path = rustic_repo_path.path();
+
+    let output = rustic_runner(rustic_repo_path, repo_password)?
+        .args(["repoinfo", "--only-files", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let infos: serde_json::Value = serde_json::from_slice(&output)?;
+
+    // the config file is not part of the listed repository files
+    let repo_dir = rustic_repo_path.join("repo");
+    let expected: u64 = ["keys", "snapshots", "index", "data"]
+        .into_iter()
+        .map(|dir| dir_size(&repo_dir.join(dir)))
+        .sum::<Result<_>>()?;
+
+    assert_eq!(infos["total_size"].as_u64(), Some(expected));
+
+    Ok(())
+}
+
 #[rstest]
 fn test_restic_repo_with_rustic_passes(restic_repo: Result<TestSource>) -> TestResult<()> {
     let restic_repo = restic_repo?;